@@ -0,0 +1,256 @@
+// A small JSONPath evaluator covering the common subset: root `$`, child
+// `.name` / `['name']`, recursive descent `..`, wildcard `*`, and array
+// index/slice `[n]` / `[start:end]`.
+use pyo3::PyErr;
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Child(String),
+    Wildcard,
+    RecursiveDescent,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+}
+
+fn invalid_path(path: &str) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(format!("Invalid JSONPath expression: {path}"))
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, PyErr> {
+    let chars: Vec<char> = path.chars().collect();
+    let n = chars.len();
+    let mut i = 0;
+    let mut segments = Vec::new();
+
+    if i < n && chars[i] == '$' {
+        i += 1;
+    }
+
+    while i < n {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                if i < n && chars[i] == '.' {
+                    segments.push(Segment::RecursiveDescent);
+                    i += 1;
+                    continue;
+                }
+                if i < n && chars[i] == '*' {
+                    segments.push(Segment::Wildcard);
+                    i += 1;
+                } else {
+                    let start = i;
+                    while i < n && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                        i += 1;
+                    }
+                    if start == i {
+                        return Err(invalid_path(path));
+                    }
+                    segments.push(Segment::Child(chars[start..i].iter().collect()));
+                }
+            }
+            '[' => {
+                i += 1;
+                let start = i;
+                while i < n && chars[i] != ']' {
+                    i += 1;
+                }
+                if i >= n {
+                    return Err(invalid_path(path));
+                }
+                let inner: String = chars[start..i].iter().collect();
+                i += 1;
+                segments.push(parse_bracket(&inner, path)?);
+            }
+            _ => {
+                let start = i;
+                while i < n && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                if start == i {
+                    return Err(invalid_path(path));
+                }
+                segments.push(Segment::Child(chars[start..i].iter().collect()));
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+fn parse_bracket(inner: &str, path: &str) -> Result<Segment, PyErr> {
+    let inner = inner.trim();
+    if inner == "*" {
+        return Ok(Segment::Wildcard);
+    }
+    if inner.len() >= 2
+        && ((inner.starts_with('\'') && inner.ends_with('\''))
+            || (inner.starts_with('"') && inner.ends_with('"')))
+    {
+        return Ok(Segment::Child(inner[1..inner.len() - 1].to_string()));
+    }
+    if let Some(colon) = inner.find(':') {
+        let (start, end) = inner.split_at(colon);
+        let end = &end[1..];
+        let start = if start.is_empty() {
+            None
+        } else {
+            Some(start.parse().map_err(|_| invalid_path(path))?)
+        };
+        let end = if end.is_empty() {
+            None
+        } else {
+            Some(end.parse().map_err(|_| invalid_path(path))?)
+        };
+        return Ok(Segment::Slice(start, end));
+    }
+    let idx: i64 = inner.parse().map_err(|_| invalid_path(path))?;
+    Ok(Segment::Index(idx))
+}
+
+fn resolve_index(len: usize, idx: i64) -> Option<usize> {
+    let resolved = if idx < 0 { len as i64 + idx } else { idx };
+    if resolved >= 0 && (resolved as usize) < len {
+        Some(resolved as usize)
+    } else {
+        None
+    }
+}
+
+fn resolve_slice_bound(len: i64, bound: Option<i64>, default: i64) -> i64 {
+    match bound {
+        None => default,
+        Some(b) if b < 0 => (len + b).clamp(0, len),
+        Some(b) => b.clamp(0, len),
+    }
+}
+
+fn collect_descendants(value: &Value) -> Vec<&Value> {
+    let mut out = vec![value];
+    match value {
+        Value::Object(map) => {
+            for v in map.values() {
+                out.extend(collect_descendants(v));
+            }
+        }
+        Value::Array(arr) => {
+            for v in arr {
+                out.extend(collect_descendants(v));
+            }
+        }
+        _ => {}
+    }
+    out
+}
+
+fn apply_segment<'v>(values: Vec<&'v Value>, segment: &Segment) -> Vec<&'v Value> {
+    match segment {
+        Segment::Child(name) => values
+            .into_iter()
+            .filter_map(|v| v.as_object().and_then(|m| m.get(name)))
+            .collect(),
+        Segment::Wildcard => values
+            .into_iter()
+            .flat_map(|v| match v {
+                Value::Object(map) => map.values().collect::<Vec<_>>(),
+                Value::Array(arr) => arr.iter().collect::<Vec<_>>(),
+                _ => Vec::new(),
+            })
+            .collect(),
+        Segment::RecursiveDescent => values.into_iter().flat_map(collect_descendants).collect(),
+        Segment::Index(idx) => values
+            .into_iter()
+            .filter_map(|v| {
+                v.as_array()
+                    .and_then(|arr| resolve_index(arr.len(), *idx))
+                    .and_then(|i| v.as_array().unwrap().get(i))
+            })
+            .collect(),
+        Segment::Slice(start, end) => values
+            .into_iter()
+            .flat_map(|v| match v.as_array() {
+                Some(arr) => {
+                    let len = arr.len() as i64;
+                    let s = resolve_slice_bound(len, *start, 0);
+                    let e = resolve_slice_bound(len, *end, len);
+                    if s >= e {
+                        Vec::new()
+                    } else {
+                        arr[s as usize..e as usize].iter().collect()
+                    }
+                }
+                None => Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+/// Evaluate a JSONPath expression against `root`, returning every matching
+/// sub-value.
+pub fn select<'v>(root: &'v Value, path: &str) -> Result<Vec<&'v Value>, PyErr> {
+    let segments = parse_path(path)?;
+    let mut current = vec![root];
+    for segment in &segments {
+        current = apply_segment(current, segment);
+    }
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn child_and_wildcard_descend_into_array_elements() {
+        let root = json!({
+            "results": [
+                {"metrics": {"a": 1}},
+                {"metrics": {"b": 2}},
+            ]
+        });
+        let matches = select(&root, "$.results[*].metrics").unwrap();
+        assert_eq!(matches, vec![&json!({"a": 1}), &json!({"b": 2})]);
+    }
+
+    #[test]
+    fn recursive_descent_finds_nested_keys() {
+        let root = json!({
+            "x": 1,
+            "nested": {"x": 2, "deeper": {"x": 3}},
+            "list": [{"x": 4}],
+        });
+        let mut matches: Vec<&Value> = select(&root, "$..x").unwrap();
+        let mut values: Vec<i64> = matches.drain(..).map(|v| v.as_i64().unwrap()).collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn slice_selects_a_subrange() {
+        let root = json!([0, 1, 2, 3, 4]);
+        let matches = select(&root, "$[1:3]").unwrap();
+        assert_eq!(matches, vec![&json!(1), &json!(2)]);
+    }
+
+    #[test]
+    fn negative_index_selects_from_the_end() {
+        let root = json!([0, 1, 2, 3, 4]);
+        let matches = select(&root, "$[-1]").unwrap();
+        assert_eq!(matches, vec![&json!(4)]);
+    }
+
+    #[test]
+    fn quoted_bracket_key_with_spaces() {
+        let root = json!({"weird key": 5});
+        let matches = select(&root, "$['weird key']").unwrap();
+        assert_eq!(matches, vec![&json!(5)]);
+    }
+
+    #[test]
+    fn invalid_path_is_an_error() {
+        let root = json!({});
+        assert!(select(&root, "$.").is_err());
+    }
+}
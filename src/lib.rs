@@ -1,8 +1,21 @@
+// This crate still uses pyo3's GIL-ref API (`&PyDict`, `&PyAny`, ...)
+// throughout rather than the newer `Bound<'_, T>` API; silence the resulting
+// deprecation warnings until a full migration is worth doing on its own.
+#![allow(deprecated)]
+
+mod jsonpath;
+mod schema;
+
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyFrozenSet, PyList, PySet, PyTuple};
+use rayon::prelude::*;
 use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet};
 
+// Children counts above this are walked with rayon's `par_iter` instead of
+// sequentially; below it the overhead of spawning work isn't worth it.
+const PARALLEL_THRESHOLD: usize = 64;
+
 // Convert PyDict to a serde_json::Value
 fn pydict_to_value(py_dict: &PyDict) -> Result<Value, PyErr> {
     let mut map = Map::new();
@@ -14,10 +27,23 @@ fn pydict_to_value(py_dict: &PyDict) -> Result<Value, PyErr> {
     Ok(Value::Object(map))
 }
 
+// Convert any Python sequence/set-like object to a serde_json::Value::Array
+fn py_sequence_to_value<'a>(items: impl Iterator<Item = &'a PyAny>) -> Result<Value, PyErr> {
+    items
+        .map(py_to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Array)
+}
+
 // Convert PyValue to a serde_json::Value
 fn py_to_value(py_value: &PyAny) -> Result<Value, PyErr> {
     if let Ok(py_dict) = py_value.downcast::<PyDict>() {
         pydict_to_value(py_dict)
+    } else if let Ok(py_bool) = py_value.extract::<bool>() {
+        // Must be checked before `i64`: Python `bool` is a subclass of `int`,
+        // so `extract::<i64>()` would otherwise succeed first and miscount
+        // `True`/`False` as Numbers.
+        Ok(Value::Bool(py_bool))
     } else if let Ok(py_str) = py_value.extract::<String>() {
         Ok(Value::String(py_str))
     } else if let Ok(py_int) = py_value.extract::<i64>() {
@@ -26,8 +52,14 @@ fn py_to_value(py_value: &PyAny) -> Result<Value, PyErr> {
         Ok(Value::Number(
             serde_json::Number::from_f64(py_float).unwrap(),
         ))
-    } else if let Ok(py_bool) = py_value.extract::<bool>() {
-        Ok(Value::Bool(py_bool))
+    } else if let Ok(py_list) = py_value.downcast::<PyList>() {
+        py_sequence_to_value(py_list.iter())
+    } else if let Ok(py_tuple) = py_value.downcast::<PyTuple>() {
+        py_sequence_to_value(py_tuple.iter())
+    } else if let Ok(py_set) = py_value.downcast::<PySet>() {
+        py_sequence_to_value(py_set.iter())
+    } else if let Ok(py_frozenset) = py_value.downcast::<PyFrozenSet>() {
+        py_sequence_to_value(py_frozenset.iter())
     } else if py_value.is_none() {
         Ok(Value::Null)
     } else {
@@ -35,101 +67,272 @@ fn py_to_value(py_value: &PyAny) -> Result<Value, PyErr> {
     }
 }
 
-// Recursively summarize a json structure
-fn summarize_value(
-    value: &Value,
-    depth: usize,
-    type_counts: &mut HashMap<String, usize>,
-    nested_dicts: &mut usize,
-    nested_lists_with_dicts: &mut usize,
-) -> (usize, HashSet<String>) {
-    let mut size = 0;
-    let mut keys = HashSet::new();
+// Accumulator for `summarize_value`. Kept as a plain, owned struct (rather
+// than `&mut` accumulators threaded through the recursion) so that parallel
+// branches can each build their own `Summary` and combine them with
+// `Summary::merge`, which is associative and commutative.
+#[derive(Default)]
+struct Summary {
+    type_counts: HashMap<String, usize>,
+    nested_dicts: usize,
+    nested_lists_with_dicts: usize,
+    size: usize,
+    keys: HashSet<String>,
+    // depth -> type name -> count, so callers can see how types distribute
+    // across levels (e.g. all strings living at depth 5).
+    depth_histogram: HashMap<usize, HashMap<String, usize>>,
+    max_depth: usize,
+    // Number of subtrees whose recursion was cut short by `max_depth`.
+    truncated: usize,
+}
+
+impl Summary {
+    fn leaf(type_name: &str, depth: usize) -> Self {
+        let mut summary = Summary {
+            size: 1,
+            max_depth: depth,
+            ..Default::default()
+        };
+        summary.record(type_name, depth);
+        summary
+    }
+
+    fn record(&mut self, type_name: &str, depth: usize) {
+        *self.type_counts.entry(type_name.to_string()).or_insert(0) += 1;
+        *self
+            .depth_histogram
+            .entry(depth)
+            .or_default()
+            .entry(type_name.to_string())
+            .or_insert(0) += 1;
+        self.max_depth = self.max_depth.max(depth);
+    }
+
+    fn merge(mut self, other: Summary) -> Summary {
+        for (type_name, count) in other.type_counts {
+            *self.type_counts.entry(type_name).or_insert(0) += count;
+        }
+        for (depth, counts) in other.depth_histogram {
+            let entry = self.depth_histogram.entry(depth).or_default();
+            for (type_name, count) in counts {
+                *entry.entry(type_name).or_insert(0) += count;
+            }
+        }
+        self.nested_dicts += other.nested_dicts;
+        self.nested_lists_with_dicts += other.nested_lists_with_dicts;
+        self.size += other.size;
+        self.keys.extend(other.keys);
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.truncated += other.truncated;
+        self
+    }
+}
+
+// Recursively summarize a json structure, releasing the GIL beforehand and
+// spreading large objects/arrays across rayon's thread pool. `max_depth`
+// bounds the work done on pathologically deep or recursive-looking
+// documents: once `depth` reaches it, a subtree's own node is still counted
+// but its children are skipped and it's marked truncated.
+fn summarize_value(value: &Value, depth: usize, max_depth: Option<usize>) -> Summary {
+    let cutoff = max_depth.is_some_and(|limit| depth >= limit);
 
     match value {
         Value::Object(map) => {
-            size += map.len();
-            *type_counts.entry("Object".to_string()).or_insert(0) += 1;
-            *nested_dicts += 1;
-            for (key, val) in map {
-                keys.insert(key.clone());
-                let (sub_size, sub_keys) = summarize_value(
-                    val,
-                    depth + 1,
-                    type_counts,
-                    nested_dicts,
-                    nested_lists_with_dicts,
-                );
-                size += sub_size;
-                keys.extend(sub_keys);
+            let mut summary = Summary {
+                size: map.len(),
+                nested_dicts: 1,
+                ..Default::default()
+            };
+            summary.record("Object", depth);
+
+            if cutoff {
+                summary.truncated = 1;
+                return summary;
             }
+
+            let child = |(key, val): (&String, &Value)| {
+                let mut child_summary = summarize_value(val, depth + 1, max_depth);
+                child_summary.keys.insert(key.clone());
+                child_summary
+            };
+            let children = if map.len() > PARALLEL_THRESHOLD {
+                // `serde_json::Map` doesn't implement rayon's parallel
+                // iterator traits directly, so collect into a `Vec` first.
+                map.iter()
+                    .collect::<Vec<_>>()
+                    .into_par_iter()
+                    .map(child)
+                    .collect::<Vec<_>>()
+            } else {
+                map.iter().map(child).collect::<Vec<_>>()
+            };
+            children.into_iter().fold(summary, Summary::merge)
         }
         Value::Array(arr) => {
-            *type_counts.entry("Array".to_string()).or_insert(0) += 1;
-            for val in arr {
+            let mut summary = Summary::default();
+            summary.record("Array", depth);
+
+            if cutoff {
+                summary.truncated = 1;
+                return summary;
+            }
+
+            let child = |val: &Value| {
+                let mut child_summary = summarize_value(val, depth + 1, max_depth);
                 if let Value::Object(_) = val {
-                    *nested_lists_with_dicts += 1;
+                    child_summary.nested_lists_with_dicts += 1;
                 }
-                let (sub_size, sub_keys) = summarize_value(
-                    val,
-                    depth + 1,
-                    type_counts,
-                    nested_dicts,
-                    nested_lists_with_dicts,
-                );
-                size += sub_size;
-                keys.extend(sub_keys);
-            }
-        }
-        Value::String(_) => {
-            *type_counts.entry("String".to_string()).or_insert(0) += 1;
-            size += 1;
-        }
-        Value::Number(_) => {
-            *type_counts.entry("Number".to_string()).or_insert(0) += 1;
-            size += 1;
-        }
-        Value::Bool(_) => {
-            *type_counts.entry("Boolean".to_string()).or_insert(0) += 1;
-            size += 1;
-        }
-        Value::Null => {
-            *type_counts.entry("Null".to_string()).or_insert(0) += 1;
-            size += 1;
+                child_summary
+            };
+            let children = if arr.len() > PARALLEL_THRESHOLD {
+                arr.par_iter().map(child).collect::<Vec<_>>()
+            } else {
+                arr.iter().map(child).collect::<Vec<_>>()
+            };
+            children.into_iter().fold(summary, Summary::merge)
         }
+        Value::String(_) => Summary::leaf("String", depth),
+        Value::Number(_) => Summary::leaf("Number", depth),
+        Value::Bool(_) => Summary::leaf("Boolean", depth),
+        Value::Null => Summary::leaf("Null", depth),
     }
-    (size, keys)
 }
 
+// When `path` selects a subtree, `depth`/`max_depth`/the depth histogram are
+// all relative to each matched root, not the original document: a
+// `path="$.deep.nested.field"` resets depth to 0 at `field`, so pairing
+// `path` with `max_depth` bounds recursion from the selected subtree, not
+// from the document root.
+#[allow(clippy::type_complexity)]
 #[pyfunction]
+#[pyo3(signature = (py_obj, path=None, max_depth=None))]
 fn summarize_large_json(
-    py_dict: &PyDict,
-) -> PyResult<(usize, Vec<String>, usize, HashMap<String, usize>, usize)> {
-    // Convert PyDict to a HashMap<String, Value>
-    let json_value = pydict_to_value(py_dict)?;
-
-    let mut type_counts = HashMap::new();
-    let mut nested_dicts = 0;
-    let mut nested_lists_with_dicts = 0;
-    let (size, keys) = summarize_value(
-        &json_value,
-        0,
-        &mut type_counts,
-        &mut nested_dicts,
-        &mut nested_lists_with_dicts,
-    );
+    py: Python<'_>,
+    py_obj: &PyAny,
+    path: Option<&str>,
+    max_depth: Option<usize>,
+) -> PyResult<(
+    usize,
+    Vec<String>,
+    usize,
+    HashMap<String, usize>,
+    usize,
+    HashMap<usize, HashMap<String, usize>>,
+    usize,
+    usize,
+)> {
+    // Convert the top-level Python object (dict, list, tuple, or set) to a Value
+    let json_value = py_to_value(py_obj)?;
+
+    // When a JSONPath is given, restrict the walk to its matching subtree(s)
+    // and merge their summaries; otherwise summarize the whole tree.
+    let roots = match path {
+        Some(expr) => jsonpath::select(&json_value, expr)?,
+        None => vec![&json_value],
+    };
+
+    // `json_value` is an owned `Value` with no Python references left in it,
+    // so the recursive (and potentially rayon-parallel) walk can run with
+    // the GIL released.
+    let summary = py.allow_threads(|| {
+        roots
+            .into_iter()
+            .map(|root| summarize_value(root, 0, max_depth))
+            .fold(Summary::default(), Summary::merge)
+    });
+
     Ok((
-        size,
-        keys.into_iter().collect(),
-        nested_dicts,
-        type_counts,
-        nested_lists_with_dicts,
+        summary.size,
+        summary.keys.into_iter().collect(),
+        summary.nested_dicts,
+        summary.type_counts,
+        summary.nested_lists_with_dicts,
+        summary.depth_histogram,
+        summary.max_depth,
+        summary.truncated,
     ))
 }
 
+/// Infer a recursive schema for a JSON-like Python object: for objects, a
+/// map of key to child schema plus a required/optional flag (derived from
+/// which keys appear in every element when the object recurs inside an
+/// array); for arrays, a unified element schema merged across items; for
+/// scalars, the leaf type with observed min/max/count.
+#[pyfunction]
+fn infer_schema(py: Python<'_>, py_obj: &PyAny) -> PyResult<PyObject> {
+    let json_value = py_to_value(py_obj)?;
+    let node = schema::infer(&json_value);
+    schema::to_pyobject(py, &node)
+}
+
 /// A Python module implemented in Rust.
 #[pymodule]
 fn data_summarizer(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(summarize_large_json, m)?)?;
+    m.add_function(wrap_pyfunction!(infer_schema, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn bools_are_counted_as_boolean_not_number() {
+        let value = json!([true, false, 1]);
+        let summary = summarize_value(&value, 0, None);
+        assert_eq!(summary.type_counts.get("Boolean"), Some(&2));
+        assert_eq!(summary.type_counts.get("Number"), Some(&1));
+    }
+
+    #[test]
+    fn top_level_tuple_set_and_frozenset_summarize_like_a_list() {
+        Python::with_gil(|py| {
+            let tuple = PyTuple::new(py, [1, 2, 3]);
+            let value = py_to_value(tuple).unwrap();
+            assert_eq!(value, json!([1, 2, 3]));
+
+            let set = PySet::new(py, [1].iter()).unwrap();
+            let value = py_to_value(set).unwrap();
+            assert_eq!(value, json!([1]));
+
+            let frozenset = PyFrozenSet::new(py, [1].iter()).unwrap();
+            let value = py_to_value(frozenset).unwrap();
+            assert_eq!(value, json!([1]));
+        });
+    }
+
+    #[test]
+    fn merge_combines_two_partial_summaries() {
+        let left = summarize_value(&json!({"a": 1}), 0, None);
+        let right = summarize_value(&json!({"b": "x"}), 0, None);
+        let merged = left.merge(right);
+
+        assert_eq!(merged.size, 4);
+        assert_eq!(merged.type_counts.get("Object"), Some(&2));
+        assert_eq!(merged.type_counts.get("Number"), Some(&1));
+        assert_eq!(merged.type_counts.get("String"), Some(&1));
+        assert_eq!(
+            merged.keys,
+            HashSet::from(["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn max_depth_truncates_but_still_counts_the_cutoff_node() {
+        let value = json!({"a": {"b": {"c": 1}}});
+
+        // depth 0 is the root object itself, so max_depth=0 cuts off
+        // immediately: the root is counted but not descended into.
+        let summary = summarize_value(&value, 0, Some(0));
+        assert_eq!(summary.truncated, 1);
+        assert_eq!(summary.type_counts.get("Object"), Some(&1));
+        assert_eq!(summary.type_counts.get("Number"), None);
+
+        let summary = summarize_value(&value, 0, Some(1));
+        assert_eq!(summary.truncated, 1);
+        assert_eq!(summary.type_counts.get("Object"), Some(&2));
+        assert_eq!(summary.type_counts.get("Number"), None);
+    }
+}
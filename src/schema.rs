@@ -0,0 +1,287 @@
+// Recursive schema inference: instead of collapsing a JSON document into
+// flat global counts, build a tree of `SchemaNode`s that mirrors its shape,
+// merging per-element schemas across arrays to work out which object keys
+// are required (present on every element) vs optional.
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub enum SchemaNode {
+    Object {
+        fields: HashMap<String, FieldSchema>,
+        // Number of elements merged into this node; a field is "required"
+        // when its own count equals this total.
+        total: usize,
+    },
+    Array {
+        element: Box<SchemaNode>,
+    },
+    Scalar {
+        ty: String,
+        count: usize,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldSchema {
+    node: SchemaNode,
+    count: usize,
+}
+
+fn scalar_node(ty: &str, observed: Option<f64>) -> SchemaNode {
+    SchemaNode::Scalar {
+        ty: ty.to_string(),
+        count: 1,
+        min: observed,
+        max: observed,
+    }
+}
+
+/// Infer the schema of a single `Value`, with array elements merged into one
+/// unified element schema.
+pub fn infer(value: &Value) -> SchemaNode {
+    match value {
+        Value::Object(map) => {
+            let fields = map
+                .iter()
+                .map(|(key, val)| {
+                    (
+                        key.clone(),
+                        FieldSchema {
+                            node: infer(val),
+                            count: 1,
+                        },
+                    )
+                })
+                .collect();
+            SchemaNode::Object { fields, total: 1 }
+        }
+        Value::Array(arr) => {
+            let element = arr
+                .iter()
+                .map(infer)
+                .reduce(merge)
+                .unwrap_or_else(|| scalar_node("Empty", None));
+            SchemaNode::Array {
+                element: Box::new(element),
+            }
+        }
+        Value::String(s) => scalar_node("String", Some(s.chars().count() as f64)),
+        Value::Number(n) => scalar_node("Number", n.as_f64()),
+        Value::Bool(_) => scalar_node("Boolean", None),
+        Value::Null => scalar_node("Null", None),
+    }
+}
+
+fn merge_min(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn merge_max(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Merge two schema nodes observed for different elements of the same array
+/// into one. Mismatched shapes collapse into a "Mixed" scalar rather than
+/// being dropped, so heterogeneous arrays are still reported honestly.
+fn merge(a: SchemaNode, b: SchemaNode) -> SchemaNode {
+    match (a, b) {
+        (
+            SchemaNode::Object {
+                fields: mut fields_a,
+                total: total_a,
+            },
+            SchemaNode::Object {
+                fields: fields_b,
+                total: total_b,
+            },
+        ) => {
+            for (key, field_b) in fields_b {
+                match fields_a.remove(&key) {
+                    Some(field_a) => fields_a.insert(
+                        key,
+                        FieldSchema {
+                            node: merge(field_a.node, field_b.node),
+                            count: field_a.count + field_b.count,
+                        },
+                    ),
+                    None => fields_a.insert(key, field_b),
+                };
+            }
+            SchemaNode::Object {
+                fields: fields_a,
+                total: total_a + total_b,
+            }
+        }
+        (SchemaNode::Array { element: elem_a }, SchemaNode::Array { element: elem_b }) => {
+            SchemaNode::Array {
+                element: Box::new(merge(*elem_a, *elem_b)),
+            }
+        }
+        (
+            SchemaNode::Scalar {
+                ty: ty_a,
+                count: count_a,
+                min: min_a,
+                max: max_a,
+            },
+            SchemaNode::Scalar {
+                ty: ty_b,
+                count: count_b,
+                min: min_b,
+                max: max_b,
+            },
+        ) => SchemaNode::Scalar {
+            ty: if ty_a == ty_b {
+                ty_a
+            } else {
+                "Mixed".to_string()
+            },
+            count: count_a + count_b,
+            min: merge_min(min_a, min_b),
+            max: merge_max(max_a, max_b),
+        },
+        (a, b) => SchemaNode::Scalar {
+            ty: "Mixed".to_string(),
+            count: node_count(&a) + node_count(&b),
+            min: None,
+            max: None,
+        },
+    }
+}
+
+fn node_count(node: &SchemaNode) -> usize {
+    match node {
+        SchemaNode::Object { total, .. } => *total,
+        SchemaNode::Array { .. } => 1,
+        SchemaNode::Scalar { count, .. } => *count,
+    }
+}
+
+/// Convert a `SchemaNode` tree into nested `PyDict`s.
+pub fn to_pyobject(py: Python<'_>, node: &SchemaNode) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    match node {
+        SchemaNode::Object { fields, total } => {
+            dict.set_item("type", "object")?;
+            let fields_dict = PyDict::new(py);
+            for (key, field) in fields {
+                fields_dict.set_item(key, field_to_pyobject(py, field, *total)?)?;
+            }
+            dict.set_item("fields", fields_dict)?;
+        }
+        SchemaNode::Array { element } => {
+            dict.set_item("type", "array")?;
+            dict.set_item("element", to_pyobject(py, element)?)?;
+        }
+        SchemaNode::Scalar {
+            ty,
+            count,
+            min,
+            max,
+        } => {
+            dict.set_item("type", ty)?;
+            dict.set_item("count", *count)?;
+            dict.set_item("min", *min)?;
+            dict.set_item("max", *max)?;
+        }
+    }
+    Ok(dict.into())
+}
+
+fn field_to_pyobject(py: Python<'_>, field: &FieldSchema, total: usize) -> PyResult<PyObject> {
+    let node_obj = to_pyobject(py, &field.node)?;
+    let dict = node_obj.downcast::<PyDict>(py)?;
+    dict.set_item("required", field.count == total)?;
+    Ok(dict.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn array_fields(node: &SchemaNode) -> (&HashMap<String, FieldSchema>, usize) {
+        match node {
+            SchemaNode::Array { element } => match element.as_ref() {
+                SchemaNode::Object { fields, total } => (fields, *total),
+                other => panic!("expected an Object element, got {other:?}"),
+            },
+            other => panic!("expected an Array, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn key_present_on_every_element_is_required() {
+        let value = json!([{"a": 1}, {"a": 2, "b": 3}]);
+        let node = infer(&value);
+        let (fields, total) = array_fields(&node);
+
+        assert_eq!(total, 2);
+        assert_eq!(fields["a"].count, 2, "present on every element");
+        assert_eq!(fields["b"].count, 1, "present on only one element");
+    }
+
+    #[test]
+    fn scalar_leaf_tracks_type_count_and_bounds() {
+        let value = json!([1, 2, 3]);
+        match infer(&value) {
+            SchemaNode::Array { element } => match *element {
+                SchemaNode::Scalar {
+                    ty,
+                    count,
+                    min,
+                    max,
+                } => {
+                    assert_eq!(ty, "Number");
+                    assert_eq!(count, 3);
+                    assert_eq!(min, Some(1.0));
+                    assert_eq!(max, Some(3.0));
+                }
+                other => panic!("expected a Scalar element, got {other:?}"),
+            },
+            other => panic!("expected an Array, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mismatched_element_types_collapse_to_mixed() {
+        let value = json!([1, "a"]);
+        match infer(&value) {
+            SchemaNode::Array { element } => match *element {
+                SchemaNode::Scalar { ty, count, .. } => {
+                    assert_eq!(ty, "Mixed");
+                    assert_eq!(count, 2);
+                }
+                other => panic!("expected a Scalar element, got {other:?}"),
+            },
+            other => panic!("expected an Array, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn nested_object_schema_recurses() {
+        let value = json!([{"a": {"x": 1}}, {"a": {"x": 2}}]);
+        let node = infer(&value);
+        let (fields, _total) = array_fields(&node);
+        match &fields["a"].node {
+            SchemaNode::Object { fields, total } => {
+                assert_eq!(*total, 2);
+                assert_eq!(fields["x"].count, 2);
+            }
+            other => panic!("expected an Object, got {other:?}"),
+        }
+    }
+}